@@ -1,5 +1,7 @@
 use std::f64::consts::PI;
 
+pub mod ntt;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Complex {
     pub real: f64,
@@ -10,6 +12,10 @@ impl Complex {
     pub fn new(real: f64, imag: f64) -> Self {
         Self { real, imag }
     }
+
+    pub fn conj(self) -> Self {
+        Self::new(self.real, -self.imag)
+    }
 }
 
 impl std::ops::Add for Complex {
@@ -56,40 +62,242 @@ impl std::ops::Mul<f64> for Complex {
     }
 }
 
-pub fn fft(arr: &mut [Complex]) {
-    fn _fft(arr: &mut [Complex]) {
-        let n = arr.len();
-        if n == 1 {
-            return;
+fn bit_reverse_permute(arr: &mut [Complex]) {
+    let n = arr.len();
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let rev = i.reverse_bits() >> (usize::BITS - bits);
+        if i < rev {
+            arr.swap(i, rev);
         }
+    }
+}
 
-        let mut a0 = Vec::with_capacity(n / 2);
-        let mut a1 = Vec::with_capacity(n / 2);
-
-        for i in 0..n / 2 {
-            a0.push(arr[2 * i]);
-            a1.push(arr[2 * i + 1]);
-        }
+fn _transform(arr: &mut [Complex], invert: bool) {
+    let n = arr.len();
+    if n == 1 {
+        return;
+    }
 
-        _fft(&mut a0);
-        _fft(&mut a1);
+    bit_reverse_permute(arr);
 
-        let ang = -2.0 * PI / n as f64;
-        let mut w = Complex::new(1.0, 0.0);
+    let sign = if invert { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let m = len / 2;
+        let ang = sign * PI / m as f64;
         let wn = Complex::new(ang.cos(), ang.sin());
 
-        for i in 0..n / 2 {
-            let p = a0[i];
-            let q = w * a1[i];
-            arr[i] = p + q;
-            arr[i + n / 2] = p - q;
-            w = w * wn;
+        for block in (0..n).step_by(len) {
+            let mut w = Complex::new(1.0, 0.0);
+            for k in block..block + m {
+                let p = arr[k];
+                let q = w * arr[k + m];
+                arr[k] = p + q;
+                arr[k + m] = p - q;
+                w = w * wn;
+            }
         }
+
+        len *= 2;
     }
+}
 
-    _fft(arr);
+pub fn fft(arr: &mut [Complex]) {
+    _transform(arr, false);
     let factor = 1.0 / (arr.len() as f64).sqrt();
     for it in arr {
         *it = *it * factor;
     }
 }
+
+pub fn ifft(arr: &mut [Complex]) {
+    _transform(arr, true);
+    let factor = 1.0 / (arr.len() as f64).sqrt();
+    for it in arr {
+        *it = *it * factor;
+    }
+}
+
+/// Packs `n` real samples into `n / 2` complex values and runs the complex
+/// `fft` on them, unpacking the result via Hermitian symmetry. Returns the
+/// non-redundant first `n / 2 + 1` bins.
+pub fn fft_real(input: &[f64]) -> Vec<Complex> {
+    let n = input.len();
+    let half = n / 2;
+
+    let mut z: Vec<Complex> = (0..half)
+        .map(|j| Complex::new(input[2 * j], input[2 * j + 1]))
+        .collect();
+    fft(&mut z);
+
+    // `fft` normalizes by 1/sqrt(n/2) for these n/2 packed points, but the
+    // unpack formula below assumes an unnormalized Z; correct for the
+    // mismatched normalization so `fft_real` agrees with `fft` on the
+    // shared bins.
+    let renormalize = (2.0_f64).sqrt();
+
+    let mut result = Vec::with_capacity(half + 1);
+    for k in 0..=half {
+        let z_k = if k == half { z[0] } else { z[k] };
+        let z_conj = if k == 0 { z[0] } else { z[half - k] }.conj();
+
+        let ang = -2.0 * PI * k as f64 / n as f64;
+        let e = Complex::new(ang.cos(), ang.sin());
+
+        let even = (z_k + z_conj) * 0.5;
+        let odd = Complex::new(0.0, -0.5) * e * (z_k - z_conj);
+        result.push((even + odd) * (1.0 / renormalize));
+    }
+    result
+}
+
+/// Naive O(n^2) direct-sum DFT, used as a correctness oracle for `fft`.
+/// Uses the same `1/sqrt(n)` normalization as `fft`.
+pub fn dft(arr: &[Complex]) -> Vec<Complex> {
+    let n = arr.len();
+    let mut result = Vec::with_capacity(n);
+
+    for k in 0..n {
+        let mut sum = Complex::new(0.0, 0.0);
+        for (j, &x) in arr.iter().enumerate() {
+            let ang = -2.0 * PI * (j * k) as f64 / n as f64;
+            let w = Complex::new(ang.cos(), ang.sin());
+            sum = sum + x * w;
+        }
+        result.push(sum);
+    }
+
+    let factor = 1.0 / (n as f64).sqrt();
+    for it in result.iter_mut() {
+        *it = *it * factor;
+    }
+    result
+}
+
+/// Naive O(n^2) direct-sum inverse DFT, mirroring `dft` with the conjugated
+/// twiddle factor. Used as a correctness oracle for `ifft`.
+pub fn idft(arr: &[Complex]) -> Vec<Complex> {
+    let n = arr.len();
+    let mut result = Vec::with_capacity(n);
+
+    for k in 0..n {
+        let mut sum = Complex::new(0.0, 0.0);
+        for (j, &x) in arr.iter().enumerate() {
+            let ang = 2.0 * PI * (j * k) as f64 / n as f64;
+            let w = Complex::new(ang.cos(), ang.sin());
+            sum = sum + x * w;
+        }
+        result.push(sum);
+    }
+
+    let factor = 1.0 / (n as f64).sqrt();
+    for it in result.iter_mut() {
+        *it = *it * factor;
+    }
+    result
+}
+
+/// Runs the 1D `fft` over each row then each column of a `height x width`
+/// row-major grid (the row-column method). Both dimensions must be powers
+/// of two.
+pub fn fft2d(data: &mut [Complex], width: usize, height: usize) {
+    assert!(width.is_power_of_two() && height.is_power_of_two());
+
+    for row in 0..height {
+        let start = row * width;
+        fft(&mut data[start..start + width]);
+    }
+
+    let mut column = vec![Complex::new(0.0, 0.0); height];
+    for col in 0..width {
+        for row in 0..height {
+            column[row] = data[row * width + col];
+        }
+        fft(&mut column);
+        for row in 0..height {
+            data[row * width + col] = column[row];
+        }
+    }
+}
+
+/// Inverse of [`fft2d`]: runs the 1D `ifft` over each row then each column.
+pub fn ifft2d(data: &mut [Complex], width: usize, height: usize) {
+    assert!(width.is_power_of_two() && height.is_power_of_two());
+
+    for row in 0..height {
+        let start = row * width;
+        ifft(&mut data[start..start + width]);
+    }
+
+    let mut column = vec![Complex::new(0.0, 0.0); height];
+    for col in 0..width {
+        for row in 0..height {
+            column[row] = data[row * width + col];
+        }
+        ifft(&mut column);
+        for row in 0..height {
+            data[row * width + col] = column[row];
+        }
+    }
+}
+
+/// Zero-pads `a` and `b` to the next power of two at or above
+/// `a.len() + b.len() - 1`, multiplies them in the frequency domain, and
+/// returns the linear convolution truncated to that length.
+pub fn convolve(a: &[Complex], b: &[Complex]) -> Vec<Complex> {
+    let result_len = a.len() + b.len() - 1;
+    let n = result_len.next_power_of_two();
+
+    let mut fa = vec![Complex::new(0.0, 0.0); n];
+    let mut fb = vec![Complex::new(0.0, 0.0); n];
+    fa[..a.len()].copy_from_slice(a);
+    fb[..b.len()].copy_from_slice(b);
+
+    fft(&mut fa);
+    fft(&mut fb);
+
+    let mut fc: Vec<Complex> = fa.iter().zip(fb.iter()).map(|(&x, &y)| x * y).collect();
+    ifft(&mut fc);
+
+    // fft/ifft are both normalized by 1/sqrt(n), so a pointwise multiply in
+    // the frequency domain needs one extra factor of sqrt(n) to recover the
+    // true, unnormalized convolution.
+    let correction = (n as f64).sqrt();
+    fc.truncate(result_len);
+    for it in fc.iter_mut() {
+        *it = *it * correction;
+    }
+    fc
+}
+
+/// Exact integer convolution built on the `ntt` module. Inputs are reduced
+/// modulo `ntt::MOD`, so results are only exact when the true convolution
+/// values stay within that modulus.
+pub fn convolve_i64(a: &[i64], b: &[i64]) -> Vec<i64> {
+    let result_len = a.len() + b.len() - 1;
+    let n = result_len.next_power_of_two();
+    let modulus = ntt::MOD as i64;
+
+    let mut fa = vec![0u64; n];
+    let mut fb = vec![0u64; n];
+    for (i, &v) in a.iter().enumerate() {
+        fa[i] = v.rem_euclid(modulus) as u64;
+    }
+    for (i, &v) in b.iter().enumerate() {
+        fb[i] = v.rem_euclid(modulus) as u64;
+    }
+
+    ntt::ntt(&mut fa);
+    ntt::ntt(&mut fb);
+
+    let mut fc: Vec<u64> = fa
+        .iter()
+        .zip(fb.iter())
+        .map(|(&x, &y)| x * y % ntt::MOD)
+        .collect();
+    ntt::intt(&mut fc);
+
+    fc.truncate(result_len);
+    fc.into_iter().map(|v| v as i64).collect()
+}
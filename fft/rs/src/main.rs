@@ -1,5 +1,9 @@
-use fft::{Complex, fft};
+use fft::{dft, fft, idft, ifft, Complex};
 use std::f64::consts::PI;
+use std::time::Instant;
+
+const REPETITIONS: u32 = 10;
+const TOLERANCE: f64 = 1e-6;
 
 fn round(n: f64) -> f64 {
     // precision = 2
@@ -17,17 +21,34 @@ fn generate_inputs(len: usize) -> Vec<Complex> {
     res
 }
 
+fn assert_close(actual: &[Complex], expected: &[Complex]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected.iter()) {
+        assert!(
+            (a.real - e.real).abs() < TOLERANCE && (a.imag - e.imag).abs() < TOLERANCE,
+            "mismatch: got {:?}, expected {:?}",
+            a,
+            e
+        );
+    }
+}
+
+fn time_repeated<F: FnMut()>(mut f: F) -> f64 {
+    let start = Instant::now();
+    for _ in 0..REPETITIONS {
+        f();
+    }
+    start.elapsed().as_secs_f64() * 1000.0 / REPETITIONS as f64
+}
+
 fn main() {
     let args: Vec<String> = std::env::args().collect();
     let size = args[1].parse::<usize>().unwrap();
-    let mut signals = generate_inputs(1 << size);
-    let start = std::time::Instant::now();
-    fft(&mut signals);
-    let end = std::time::Instant::now();
+    let inputs = generate_inputs(1 << size);
 
     if args.len() > 2 {
         let content = std::fs::read_to_string(args[2].clone()).unwrap();
-        let input = content
+        let expected = content
             .lines()
             .map(|l| {
                 let (re, im) = l.split_once(',').unwrap();
@@ -36,14 +57,49 @@ fn main() {
                 Complex::new(re, im)
             })
             .collect::<Vec<_>>();
-        for (i, signal) in signals.iter().enumerate() {
-            let expected = input[i];
-            assert_eq!(signal, &expected);
+
+        let mut signals = inputs.clone();
+        fft(&mut signals);
+        for (signal, expected) in signals.iter().zip(expected.iter()) {
+            assert_eq!(signal, expected);
         }
-    } else {
-        println!(
-            "execution time: {} ms",
-            end.duration_since(start).as_millis()
-        );
+        return;
     }
+
+    let via_dft = dft(&inputs);
+
+    let mut via_fft = inputs.clone();
+    fft(&mut via_fft);
+    assert_close(&via_fft, &via_dft);
+
+    let via_idft = idft(&via_dft);
+    assert_close(&via_idft, &inputs);
+
+    let mut via_ifft = via_fft.clone();
+    ifft(&mut via_ifft);
+    assert_close(&via_ifft, &inputs);
+
+    let dft_time = time_repeated(|| {
+        dft(&inputs);
+    });
+    let fft_time = time_repeated(|| {
+        let mut signals = inputs.clone();
+        fft(&mut signals);
+    });
+    let idft_time = time_repeated(|| {
+        idft(&via_dft);
+    });
+    let ifft_time = time_repeated(|| {
+        let mut signals = via_fft.clone();
+        ifft(&mut signals);
+    });
+
+    println!(
+        "size: 2^{size} ({} samples), {REPETITIONS} repetitions",
+        1 << size
+    );
+    println!("dft:  {dft_time:.3} ms");
+    println!("fft:  {fft_time:.3} ms");
+    println!("idft: {idft_time:.3} ms");
+    println!("ifft: {ifft_time:.3} ms");
 }
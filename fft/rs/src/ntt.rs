@@ -0,0 +1,73 @@
+//! Number-theoretic transform over the NTT-friendly prime `998244353`.
+//!
+//! Mirrors the iterative complex FFT in [`crate`], but replaces the twiddle
+//! factors with powers of a root of unity modulo the prime, giving
+//! bit-exact results for integer convolution.
+
+pub const MOD: u64 = 998244353;
+const PRIMITIVE_ROOT: u64 = 3;
+
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    let mut result = 1u64;
+    let mut base = base % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result
+}
+
+fn bit_reverse_permute(arr: &mut [u64]) {
+    let n = arr.len();
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let rev = i.reverse_bits() >> (usize::BITS - bits);
+        if i < rev {
+            arr.swap(i, rev);
+        }
+    }
+}
+
+fn _transform(arr: &mut [u64], invert: bool) {
+    let n = arr.len();
+    if n == 1 {
+        return;
+    }
+
+    bit_reverse_permute(arr);
+
+    let mut len = 2;
+    while len <= n {
+        let m = len / 2;
+        let w = mod_pow(PRIMITIVE_ROOT, (MOD - 1) / len as u64, MOD);
+        let w = if invert { mod_pow(w, MOD - 2, MOD) } else { w };
+
+        for block in (0..n).step_by(len) {
+            let mut wk = 1u64;
+            for k in block..block + m {
+                let u = arr[k];
+                let v = arr[k + m] * wk % MOD;
+                arr[k] = (u + v) % MOD;
+                arr[k + m] = (u + MOD - v) % MOD;
+                wk = wk * w % MOD;
+            }
+        }
+
+        len *= 2;
+    }
+}
+
+pub fn ntt(arr: &mut [u64]) {
+    _transform(arr, false);
+}
+
+pub fn intt(arr: &mut [u64]) {
+    _transform(arr, true);
+    let n_inv = mod_pow(arr.len() as u64, MOD - 2, MOD);
+    for it in arr.iter_mut() {
+        *it = *it * n_inv % MOD;
+    }
+}